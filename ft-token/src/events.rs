@@ -0,0 +1,124 @@
+use near_sdk::json_types::U128;
+use near_sdk::serde::Serialize;
+use near_sdk::{env, AccountId};
+
+use crate::TokenId;
+
+/// Enum that represents the data type of the EVENT_JSON that get logged.
+/// The standard and version fields match the NEP-245 multi-token standard.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+enum Nep245EventVariant<'a> {
+    MtMint(&'a [MtMint<'a>]),
+    MtTransfer(&'a [MtTransfer<'a>]),
+    MtBurn(&'a [MtBurn<'a>]),
+}
+
+/// Enum that represents the data type of the EVENT_JSON that get logged.
+/// The standard and version fields match the NEP-141 fungible token standard.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+enum Nep141EventVariant<'a> {
+    FtTransfer(&'a [FtTransfer<'a>]),
+}
+
+/// An event log to capture token minting.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtMint<'a> {
+    pub owner_id: &'a AccountId,
+    pub token_ids: &'a [TokenId],
+    pub amounts: &'a [U128],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<&'a str>,
+}
+
+/// An event log to capture tokens transferred between accounts.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtTransfer<'a> {
+    pub old_owner_id: &'a AccountId,
+    pub new_owner_id: &'a AccountId,
+    pub token_ids: &'a [TokenId],
+    pub amounts: &'a [U128],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<&'a str>,
+}
+
+/// An event log to capture tokens burned by their owner.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtBurn<'a> {
+    pub owner_id: &'a AccountId,
+    pub token_ids: &'a [TokenId],
+    pub amounts: &'a [U128],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<&'a str>,
+}
+
+/// An event log to capture a single-token (`DEFAULT_TOKEN_ID`) transfer leg, for callers that
+/// still speak NEP-141 (e.g. `ft_transfer_with_reference`'s fee split).
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtTransfer<'a> {
+    pub old_owner_id: &'a AccountId,
+    pub new_owner_id: &'a AccountId,
+    pub amount: U128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventLog<'a, T> {
+    standard: &'a str,
+    version: &'a str,
+    #[serde(flatten)]
+    event: T,
+}
+
+fn emit_nep245(event: Nep245EventVariant) {
+    let log = EventLog {
+        standard: "nep245",
+        version: "1.0.0",
+        event,
+    };
+    env::log_str(&format!("EVENT_JSON:{}", near_sdk::serde_json::to_string(&log).unwrap()));
+}
+
+fn emit_nep141(event: Nep141EventVariant) {
+    let log = EventLog {
+        standard: "nep141",
+        version: "1.0.0",
+        event,
+    };
+    env::log_str(&format!("EVENT_JSON:{}", near_sdk::serde_json::to_string(&log).unwrap()));
+}
+
+impl MtMint<'_> {
+    pub fn emit(self) {
+        emit_nep245(Nep245EventVariant::MtMint(&[self]));
+    }
+}
+
+impl MtTransfer<'_> {
+    pub fn emit(self) {
+        emit_nep245(Nep245EventVariant::MtTransfer(&[self]));
+    }
+}
+
+impl MtBurn<'_> {
+    pub fn emit(self) {
+        emit_nep245(Nep245EventVariant::MtBurn(&[self]));
+    }
+}
+
+impl FtTransfer<'_> {
+    pub fn emit(self) {
+        emit_nep141(Nep141EventVariant::FtTransfer(&[self]));
+    }
+}
@@ -0,0 +1,21 @@
+use near_sdk::json_types::U128;
+use near_sdk::{ext_contract, AccountId, Gas};
+
+/// Gas allocated to the receiver's `ft_on_transfer` call made by `ft_transfer_call`.
+pub const GAS_FOR_FT_ON_TRANSFER: Gas = 25_000_000_000_000;
+/// Gas allocated to `ft_resolve_transfer`, chained after `ft_on_transfer` returns.
+pub const GAS_FOR_FT_RESOLVE_TRANSFER: Gas = 15_000_000_000_000;
+
+/// Callback implemented by contracts that want to receive `DEFAULT_TOKEN_ID` via
+/// `ft_transfer_call`, per NEP-141.
+#[ext_contract(ext_ft_receiver)]
+pub trait FungibleTokenReceiver {
+    fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) -> U128;
+}
+
+/// Private callback on this contract that settles `ft_transfer_call`, refunding whatever
+/// `ft_on_transfer` reported as unused back to the sender.
+#[ext_contract(ext_self_ft)]
+pub trait FungibleTokenResolver {
+    fn ft_resolve_transfer(&mut self, sender_id: AccountId, receiver_id: AccountId, delivered: U128) -> U128;
+}
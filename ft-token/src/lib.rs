@@ -1,31 +1,117 @@
 use near_contract_standards::fungible_token::metadata::{
     FungibleTokenMetadata, FungibleTokenMetadataProvider,
 };
-use near_contract_standards::fungible_token::FungibleToken;
+use near_contract_standards::storage_management::{
+    StorageBalance, StorageBalanceBounds, StorageManagement,
+};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::LazyOption;
+use near_sdk::collections::LookupMap;
 use near_sdk::json_types::{ValidAccountId, U128};
-use near_sdk::{env, BorshStorageKey, near_bindgen, AccountId, PanicOnDefault, PromiseOrValue, Balance, Promise};
+use near_sdk::{
+    env, near_bindgen, AccountId, Balance, BorshStorageKey, Gas, PanicOnDefault, Promise,
+    PromiseOrValue, PromiseResult, StorageUsage,
+};
+
+mod events;
+mod fungible_token;
+mod multi_token;
+mod rbac;
+use events::{FtTransfer, MtBurn, MtMint, MtTransfer};
+use fungible_token::{ext_ft_receiver, ext_self_ft, GAS_FOR_FT_ON_TRANSFER, GAS_FOR_FT_RESOLVE_TRANSFER};
+use multi_token::{ext_mt_receiver, ext_self, GAS_FOR_MT_ON_TRANSFER, GAS_FOR_RESOLVE_TRANSFER};
+use rbac::Role;
 
 near_sdk::setup_alloc!();
 
+/// Identifies one of the token types a `Contract` can issue.
+pub type TokenId = String;
+
+/// The token minted to `owner_id` for `total_supply` by `new`, kept for accounts that only
+/// ever deal with a single token type.
+pub const DEFAULT_TOKEN_ID: &str = "0";
+
+/// Gas `upgrade` holds back for its own execution; the `migrate` call chained after
+/// `deploy_contract` is given whatever prepaid gas remains beyond this.
+const GAS_FOR_MIGRATE: Gas = 20_000_000_000_000;
+
+/// Cost of storing one byte of contract state, matching the protocol's storage staking price.
+/// Used to size the storage deposit `storage_deposit` requires before an account can receive
+/// tokens, per NEP-145.
+const STORAGE_PRICE_PER_BYTE: Balance = 10_000_000_000_000_000_000;
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
-    token: FungibleToken,
-    metadata: LazyOption<FungibleTokenMetadata>,
+    balances: LookupMap<TokenId, LookupMap<AccountId, Balance>>,
+    total_supply: LookupMap<TokenId, Balance>,
+    token_metadata: LookupMap<TokenId, FungibleTokenMetadata>,
+    owner_id: AccountId,
+    roles: LookupMap<AccountId, Role>,
+    paused: bool,
+    /// Protocol fee taken out of every transfer, in basis points (1/100 of a percent).
+    fee_basis_points: u16,
+    /// Account credited with `fee_basis_points` of every transfer.
+    fee_recipient: AccountId,
+    /// NEP-145 storage deposits, keyed by registered account. An account must appear here
+    /// before it can be credited with any token, so per-account balances don't grow contract
+    /// storage with nothing backing the cost.
+    accounts_storage: LookupMap<AccountId, Balance>,
+    /// Bytes one entry in `accounts_storage` costs, measured once at `new`/`migrate` time and
+    /// used to compute `storage_balance_bounds`.
+    account_storage_usage: StorageUsage,
 }
 
 #[derive(BorshSerialize, BorshStorageKey)]
 enum StorageKey {
-    Token,
-    Metadata,
+    Balances,
+    BalancesInner { token_id_hash: Vec<u8> },
+    TotalSupply,
+    TokenMetadata,
+    Roles,
+    AccountsStorage,
+}
+
+/// Mirrors the on-chain layout of `Contract` prior to the in-flight upgrade, so `migrate`
+/// can deserialize whatever state is currently stored and reshape it into the new layout.
+#[derive(BorshDeserialize)]
+struct OldContract {
+    balances: LookupMap<TokenId, LookupMap<AccountId, Balance>>,
+    total_supply: LookupMap<TokenId, Balance>,
+    token_metadata: LookupMap<TokenId, FungibleTokenMetadata>,
+    owner_id: AccountId,
+    roles: LookupMap<AccountId, Role>,
+    paused: bool,
+}
+
+impl From<OldContract> for Contract {
+    /// Carries everything over unchanged, defaults the new fee fields to "no fee" so an upgrade
+    /// from a pre-fee deployment doesn't start charging senders without an explicit `set_fee`
+    /// call, and starts `accounts_storage` fresh with only `owner_id` registered. Any other
+    /// account that already held a balance under the old layout keeps it, but must call
+    /// `storage_deposit` before receiving further transfers, same as a brand new account would.
+    fn from(old: OldContract) -> Self {
+        let mut this = Self {
+            balances: old.balances,
+            total_supply: old.total_supply,
+            token_metadata: old.token_metadata,
+            owner_id: old.owner_id.clone(),
+            roles: old.roles,
+            paused: old.paused,
+            fee_basis_points: 0,
+            fee_recipient: old.owner_id.clone(),
+            accounts_storage: LookupMap::new(StorageKey::AccountsStorage),
+            account_storage_usage: 0,
+        };
+        this.account_storage_usage = this.measure_account_storage_usage();
+        this.internal_register_account(&old.owner_id);
+        this
+    }
 }
 
 #[near_bindgen]
 impl Contract {
-    /// Initializes the contract with the given total supply owned by the given `owner_id` with
-    /// the given fungible token metadata.
+    /// Initializes the contract, minting `total_supply` of `DEFAULT_TOKEN_ID` to `owner_id` with
+    /// the given metadata. Additional token types can be created later with `mt_mint`.
     #[init]
     pub fn new(
         owner_id: ValidAccountId,
@@ -34,62 +120,731 @@ impl Contract {
     ) -> Self {
         metadata.assert_valid();
         let mut this = Self {
-            token: FungibleToken::new(StorageKey::Token),
-            metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
+            balances: LookupMap::new(StorageKey::Balances),
+            total_supply: LookupMap::new(StorageKey::TotalSupply),
+            token_metadata: LookupMap::new(StorageKey::TokenMetadata),
+            owner_id: owner_id.as_ref().clone(),
+            roles: LookupMap::new(StorageKey::Roles),
+            paused: false,
+            fee_basis_points: 0,
+            fee_recipient: owner_id.as_ref().clone(),
+            accounts_storage: LookupMap::new(StorageKey::AccountsStorage),
+            account_storage_usage: 0,
         };
-        this.token.internal_register_account(owner_id.as_ref());
-        this.token.internal_deposit(owner_id.as_ref(), total_supply.into());
+        this.account_storage_usage = this.measure_account_storage_usage();
+        this.internal_register_account(owner_id.as_ref());
+        this.token_metadata
+            .insert(&DEFAULT_TOKEN_ID.to_string(), &metadata);
+        this.internal_deposit(&DEFAULT_TOKEN_ID.to_string(), owner_id.as_ref(), total_supply.0);
         this
     }
 
+    /// Grants `role` to `account_id`. Callable only by an account holding `Role::Admin`
+    /// (the owner always does).
+    pub fn grant_role(&mut self, account_id: ValidAccountId, role: Role) {
+        self.assert_has_role(Role::Admin);
+        self.roles.insert(account_id.as_ref(), &role);
+    }
+
+    /// Revokes any role held by `account_id`. Callable only by an account holding
+    /// `Role::Admin` (the owner always does).
+    pub fn revoke_role(&mut self, account_id: ValidAccountId) {
+        self.assert_has_role(Role::Admin);
+        self.roles.remove(account_id.as_ref());
+    }
+
+    /// Sets the protocol fee taken out of every transfer. Owner-only. Registers `fee_recipient`
+    /// for storage if it isn't already, so the very next transfer doesn't fail to deposit the fee.
+    pub fn set_fee(&mut self, fee_basis_points: u16, fee_recipient: ValidAccountId) {
+        self.assert_owner();
+        assert!(fee_basis_points <= 10_000, "fee_basis_points must be between 0 and 10000");
+        self.fee_basis_points = fee_basis_points;
+        self.fee_recipient = fee_recipient.as_ref().clone();
+        self.internal_register_account(fee_recipient.as_ref());
+    }
+
+    /// Mints `amount` of `token_id` to `account_id`. If `token_id` has never been minted before,
+    /// `metadata` must be provided to describe it; it is ignored on subsequent mints of the same
+    /// `token_id`. Callable only by an account holding `Role::Minter`. `account_id` must already
+    /// be registered via `storage_deposit`.
+    pub fn mt_mint(
+        &mut self,
+        token_id: TokenId,
+        account_id: ValidAccountId,
+        amount: U128,
+        metadata: Option<FungibleTokenMetadata>,
+    ) {
+        self.assert_not_paused();
+        self.assert_has_role(Role::Minter);
+        self.assert_registered(account_id.as_ref());
+        if self.token_metadata.get(&token_id).is_none() {
+            let metadata = metadata.expect("Error: metadata is required to mint a new token_id");
+            metadata.assert_valid();
+            self.token_metadata.insert(&token_id, &metadata);
+        }
+        self.internal_deposit(&token_id, account_id.as_ref(), amount.0);
+        MtMint {
+            owner_id: account_id.as_ref(),
+            token_ids: &[token_id],
+            amounts: &[amount],
+            memo: None,
+        }
+        .emit();
+    }
+
+    /// Burns `amount` of `token_id` from the caller's own balance. Self-service complement to
+    /// `mt_mint`, and the multi-token replacement for the original single-token `withdraw`: since
+    /// `mt_mint` no longer requires an attached deposit backing the minted amount 1:1, burning no
+    /// longer returns NEAR, but the burn is still recorded with an event so redemptions elsewhere
+    /// (or simple supply reduction) remain auditable off-chain.
     #[payable]
-    pub fn mint(&mut self, account_id: ValidAccountId) {
-        let amount: Balance = env::attached_deposit();
-        self.token.internal_deposit(account_id.as_ref(), amount);
+    pub fn mt_burn(&mut self, token_id: TokenId, amount: U128) {
+        near_sdk::assert_one_yocto();
+        self.assert_not_paused();
+        let account_id = env::predecessor_account_id();
+        self.internal_withdraw(&token_id, &account_id, amount.0);
+        MtBurn {
+            owner_id: &account_id,
+            token_ids: &[token_id],
+            amounts: &[amount],
+            memo: None,
+        }
+        .emit();
     }
 
-    pub fn withdraw(&mut self, amount: U128) -> Promise {
-        let account_id: AccountId = env::predecessor_account_id();
-        self.token.internal_withdraw(&account_id, amount.0);
-        Promise::new(account_id).transfer(amount.0)
+    /// Returns the balance of `account_id` for `token_id`.
+    pub fn mt_balance_of(&self, account_id: ValidAccountId, token_id: TokenId) -> U128 {
+        U128(self.balance_of(&token_id, account_id.as_ref()))
+    }
+
+    /// Returns the total minted supply of `token_id`.
+    pub fn mt_total_supply(&self, token_id: TokenId) -> U128 {
+        U128(self.total_supply.get(&token_id).unwrap_or(0))
+    }
+
+    /// Returns the balance of `account_id` for `DEFAULT_TOKEN_ID`, per NEP-141.
+    pub fn ft_balance_of(&self, account_id: ValidAccountId) -> U128 {
+        self.mt_balance_of(account_id, DEFAULT_TOKEN_ID.to_string())
+    }
+
+    /// Returns the total minted supply of `DEFAULT_TOKEN_ID`, per NEP-141.
+    pub fn ft_total_supply(&self) -> U128 {
+        self.mt_total_supply(DEFAULT_TOKEN_ID.to_string())
     }
-}
 
-near_contract_standards::impl_fungible_token_core!(Contract, token);
-near_contract_standards::impl_fungible_token_storage!(Contract, token);
+    /// Returns the metadata `token_id` was minted with, if it exists.
+    pub fn mt_metadata(&self, token_id: TokenId) -> Option<FungibleTokenMetadata> {
+        self.token_metadata.get(&token_id)
+    }
+
+    /// Transfers `amount` of `DEFAULT_TOKEN_ID` to `receiver_id`, logging the sha256 of
+    /// `payment_reference` alongside the transfer so off-chain systems can reconcile the
+    /// transfer against an invoice without storing the reference itself on chain.
+    #[payable]
+    pub fn ft_transfer_with_reference(
+        &mut self,
+        receiver_id: ValidAccountId,
+        amount: U128,
+        payment_reference: String,
+    ) {
+        near_sdk::assert_one_yocto();
+        self.assert_not_paused();
+        let sender_id = env::predecessor_account_id();
+        let receiver_id: AccountId = receiver_id.into();
+        self.assert_registered(&receiver_id);
+        self.internal_ft_transfer_with_fee(&sender_id, &receiver_id, amount.0, None);
+        env::log_str(&format!(
+            "payment_reference_hash:{}",
+            to_hex(&env::sha256(payment_reference.as_bytes()))
+        ));
+    }
+
+    /// Transfers `amount` of `DEFAULT_TOKEN_ID` to `receiver_id`, per NEP-141. Deducts the
+    /// protocol fee set by `set_fee`, same as every other transfer entrypoint.
+    #[payable]
+    pub fn ft_transfer(&mut self, receiver_id: ValidAccountId, amount: U128, memo: Option<String>) {
+        near_sdk::assert_one_yocto();
+        self.assert_not_paused();
+        let sender_id = env::predecessor_account_id();
+        let receiver_id: AccountId = receiver_id.into();
+        self.assert_registered(&receiver_id);
+        self.internal_ft_transfer_with_fee(&sender_id, &receiver_id, amount.0, memo);
+    }
+
+    /// Transfers `amount` of `DEFAULT_TOKEN_ID` to `receiver_id`, then calls
+    /// `receiver_id.ft_on_transfer`, per NEP-141. Mirrors `mt_batch_transfer_call`: the receiver
+    /// is notified of (and `ft_resolve_transfer` refunds out of) the post-fee amount actually
+    /// delivered, so the fee is never refunded.
+    #[payable]
+    pub fn ft_transfer_call(
+        &mut self,
+        receiver_id: ValidAccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        near_sdk::assert_one_yocto();
+        self.assert_not_paused();
+        let sender_id = env::predecessor_account_id();
+        let receiver_id: AccountId = receiver_id.into();
+        self.assert_registered(&receiver_id);
+        let delivered = self.internal_ft_transfer_with_fee(&sender_id, &receiver_id, amount.0, memo);
+
+        ext_ft_receiver::ft_on_transfer(
+            sender_id.clone(),
+            delivered.into(),
+            msg,
+            receiver_id.clone(),
+            0,
+            GAS_FOR_FT_ON_TRANSFER,
+        )
+        .then(ext_self_ft::ft_resolve_transfer(
+            sender_id,
+            receiver_id,
+            delivered.into(),
+            env::current_account_id(),
+            0,
+            GAS_FOR_FT_RESOLVE_TRANSFER,
+        ))
+        .into()
+    }
+
+    /// Settles `ft_transfer_call`: whatever `ft_on_transfer` reported as unused (and the receiver
+    /// still holds) is transferred back to `sender_id`. `delivered` is the post-fee amount the
+    /// receiver was credited with, so the fee stays with `fee_recipient` regardless of how much
+    /// the receiver consumed. Returns the amount of `delivered` actually used, per NEP-141.
+    #[private]
+    pub fn ft_resolve_transfer(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        delivered: U128,
+    ) -> U128 {
+        let unused_amount = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                if let Ok(unused) = near_sdk::serde_json::from_slice::<U128>(&value) {
+                    std::cmp::min(unused.0, delivered.0)
+                } else {
+                    delivered.0
+                }
+            }
+            PromiseResult::Failed | PromiseResult::NotReady => delivered.0,
+        };
+
+        let token_id = DEFAULT_TOKEN_ID.to_string();
+        let refund = std::cmp::min(unused_amount, self.balance_of(&token_id, &receiver_id));
+        if refund > 0 {
+            self.internal_transfer(&token_id, &receiver_id, &sender_id, refund, None);
+        }
+        U128(delivered.0 - refund)
+    }
+
+    /// Transfers `amounts[i]` of `token_ids[i]` from the caller to `receiver_id` for each `i`,
+    /// deducting the protocol fee set by `set_fee` from each leg.
+    #[payable]
+    pub fn mt_batch_transfer(
+        &mut self,
+        receiver_id: ValidAccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        memo: Option<String>,
+    ) {
+        near_sdk::assert_one_yocto();
+        self.assert_not_paused();
+        let sender_id = env::predecessor_account_id();
+        let receiver_id: AccountId = receiver_id.into();
+        self.assert_registered(&receiver_id);
+        self.internal_batch_transfer_with_fee(&sender_id, &receiver_id, &token_ids, &amounts, memo);
+    }
+
+    /// Transfers `amounts[i]` of `token_ids[i]` from the caller to `receiver_id`, deducting the
+    /// protocol fee set by `set_fee` from each leg exactly like `mt_batch_transfer`, then calls
+    /// `receiver_id.mt_on_transfer` with the post-fee amounts actually deposited. Any amount the
+    /// receiver reports as unused is refunded back to the caller via `mt_resolve_transfer`; the
+    /// fee itself is never refunded, so a sender can't dodge it by routing through this entrypoint
+    /// instead of `mt_batch_transfer`.
+    #[payable]
+    pub fn mt_batch_transfer_call(
+        &mut self,
+        receiver_id: ValidAccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<Vec<U128>> {
+        near_sdk::assert_one_yocto();
+        self.assert_not_paused();
+        let sender_id = env::predecessor_account_id();
+        let receiver_id: AccountId = receiver_id.into();
+        self.assert_registered(&receiver_id);
+        let delivered =
+            self.internal_batch_transfer_with_fee(&sender_id, &receiver_id, &token_ids, &amounts, memo);
+
+        ext_mt_receiver::mt_on_transfer(
+            sender_id.clone(),
+            token_ids.clone(),
+            delivered.clone(),
+            msg,
+            receiver_id.clone(),
+            0,
+            GAS_FOR_MT_ON_TRANSFER,
+        )
+        .then(ext_self::mt_resolve_transfer(
+            sender_id,
+            receiver_id,
+            token_ids,
+            delivered,
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_TRANSFER,
+        ))
+        .into()
+    }
+
+    /// Settles `mt_batch_transfer_call`: whatever `mt_on_transfer` reported as unused (and the
+    /// receiver still holds) is transferred back to `sender_id`. `delivered` is the post-fee
+    /// amount the receiver was credited with, so the fee itself is never part of the refund and
+    /// stays with `fee_recipient` regardless of how much the receiver consumed. Returns the
+    /// amounts of `delivered` actually used, per NEP-245.
+    #[private]
+    pub fn mt_resolve_transfer(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        delivered: Vec<U128>,
+    ) -> Vec<U128> {
+        let unused_amounts: Vec<U128> = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                if let Ok(unused) = near_sdk::serde_json::from_slice::<Vec<U128>>(&value) {
+                    unused
+                        .into_iter()
+                        .zip(delivered.iter())
+                        .map(|(unused, amount)| U128(std::cmp::min(unused.0, amount.0)))
+                        .collect()
+                } else {
+                    delivered.clone()
+                }
+            }
+            PromiseResult::Failed | PromiseResult::NotReady => delivered.clone(),
+        };
+
+        let mut used_amounts = Vec::with_capacity(delivered.len());
+        for ((token_id, amount), unused) in
+            token_ids.iter().zip(delivered.iter()).zip(unused_amounts.iter())
+        {
+            let refund = std::cmp::min(unused.0, self.balance_of(token_id, &receiver_id));
+            if refund > 0 {
+                self.internal_transfer(token_id, &receiver_id, &sender_id, refund, None);
+            }
+            used_amounts.push(U128(amount.0 - refund));
+        }
+        used_amounts
+    }
+
+    /// Deploys the Wasm code passed via `env::input()` to this account and chains a call to
+    /// `migrate` so state is reshaped before any other method can run against it. Owner-only,
+    /// so a compromised minter or admin can't swap the running code.
+    pub fn upgrade(&self) -> Promise {
+        self.assert_owner();
+        let code = env::input().expect("Error: No upgrade code provided");
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .then(Promise::new(env::current_account_id()).function_call(
+                "migrate".to_string(),
+                Vec::new(),
+                0,
+                env::prepaid_gas() - env::used_gas() - GAS_FOR_MIGRATE,
+            ))
+    }
+
+    /// Reshapes state left over from a previous contract layout into the current `Contract`.
+    /// Only callable by the contract itself, as the second leg of `upgrade`.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old: OldContract =
+            env::state_read().expect("Error: failed to read old contract state");
+        old.into()
+    }
+
+    /// Pauses `mt_mint` and transfers. Owner-only circuit breaker for incident response.
+    pub fn pause(&mut self) {
+        self.assert_owner();
+        self.paused = true;
+    }
+
+    /// Lifts a previous `pause()`. Owner-only.
+    pub fn unpause(&mut self) {
+        self.assert_owner();
+        self.paused = false;
+    }
+}
 
 #[near_bindgen]
 impl FungibleTokenMetadataProvider for Contract {
     fn ft_metadata(&self) -> FungibleTokenMetadata {
-        self.metadata.get().unwrap()
+        self.mt_metadata(DEFAULT_TOKEN_ID.to_string())
+            .expect("Error: DEFAULT_TOKEN_ID was not minted with metadata")
+    }
+}
+
+/// NEP-145 storage registration: an account must hold a deposit here before any of the transfer
+/// or `mt_mint` entrypoints will credit it with a token, so per-account balances can't grow
+/// contract storage for free. This contract only supports registration-only deposits (`min ==
+/// max`), so there's never an above-minimum balance to withdraw.
+#[near_bindgen]
+impl StorageManagement for Contract {
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<ValidAccountId>,
+        _registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = env::attached_deposit();
+        let account_id: AccountId =
+            account_id.map(|a| a.as_ref().clone()).unwrap_or_else(env::predecessor_account_id);
+        let min_balance = self.storage_balance_bounds().min.0;
+
+        if self.accounts_storage.contains_key(&account_id) {
+            if amount > 0 {
+                Promise::new(env::predecessor_account_id()).transfer(amount);
+            }
+        } else {
+            assert!(amount >= min_balance, "The attached deposit is less than the minimum storage balance");
+            self.accounts_storage.insert(&account_id, &min_balance);
+            let refund = amount - min_balance;
+            if refund > 0 {
+                Promise::new(env::predecessor_account_id()).transfer(refund);
+            }
+        }
+        self.storage_balance_of_account(&account_id).unwrap()
+    }
+
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        near_sdk::assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let balance = self
+            .storage_balance_of_account(&account_id)
+            .unwrap_or_else(|| env::panic_str("The account is not registered"));
+        if let Some(amount) = amount {
+            assert_eq!(
+                amount.0, 0,
+                "This contract only supports the minimum storage balance; nothing is available to withdraw"
+            );
+        }
+        balance
+    }
+
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        near_sdk::assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        match self.accounts_storage.get(&account_id) {
+            Some(storage_balance) => {
+                let default_balance = self.balance_of(&DEFAULT_TOKEN_ID.to_string(), &account_id);
+                assert!(
+                    default_balance == 0 || force.unwrap_or(false),
+                    "Can't unregister the account with a positive DEFAULT_TOKEN_ID balance without force=true"
+                );
+                self.accounts_storage.remove(&account_id);
+                Promise::new(account_id).transfer(storage_balance);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        let required_balance =
+            Balance::from(self.account_storage_usage) * STORAGE_PRICE_PER_BYTE;
+        StorageBalanceBounds { min: required_balance.into(), max: Some(required_balance.into()) }
+    }
+
+    fn storage_balance_of(&self, account_id: ValidAccountId) -> Option<StorageBalance> {
+        self.storage_balance_of_account(account_id.as_ref())
     }
 }
 
+impl Contract {
+    fn has_role(&self, account_id: &AccountId, role: Role) -> bool {
+        account_id == &self.owner_id
+            || self.roles.get(account_id).map_or(false, |held| held.satisfies(role))
+    }
+
+    fn assert_has_role(&self, role: Role) {
+        let predecessor = env::predecessor_account_id();
+        assert!(self.has_role(&predecessor, role), "Caller does not have the required role");
+    }
+
+    fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only the contract owner can call this method"
+        );
+    }
+
+    fn assert_not_paused(&self) {
+        assert!(!self.paused, "Contract is paused");
+    }
+
+    fn assert_registered(&self, account_id: &AccountId) {
+        assert!(
+            self.accounts_storage.contains_key(account_id),
+            "The account {} is not registered; call storage_deposit first",
+            account_id
+        );
+    }
+
+    /// Registers `account_id` for storage without requiring a deposit from it. Used for accounts
+    /// the contract already trusts to exist (the owner at `new`/`migrate` time, `fee_recipient`
+    /// at `set_fee` time) rather than ones that must pay their own way via `storage_deposit`.
+    fn internal_register_account(&mut self, account_id: &AccountId) {
+        if !self.accounts_storage.contains_key(account_id) {
+            let min_balance = self.storage_balance_bounds().min.0;
+            self.accounts_storage.insert(account_id, &min_balance);
+        }
+    }
+
+    fn storage_balance_of_account(&self, account_id: &AccountId) -> Option<StorageBalance> {
+        self.accounts_storage
+            .get(account_id)
+            .map(|total| StorageBalance { total: total.into(), available: U128(0) })
+    }
+
+    /// Measures the marginal storage `accounts_storage` costs per entry, by inserting then
+    /// removing a placeholder with the longest possible `AccountId`. Called once at `new`/
+    /// `migrate` time; the result backs `storage_balance_bounds` for the life of the contract.
+    fn measure_account_storage_usage(&mut self) -> StorageUsage {
+        let initial_storage_usage = env::storage_usage();
+        let tmp_account_id: AccountId = "a".repeat(64);
+        self.accounts_storage.insert(&tmp_account_id, &0);
+        let usage = env::storage_usage() - initial_storage_usage;
+        self.accounts_storage.remove(&tmp_account_id);
+        usage
+    }
+
+    fn balance_of(&self, token_id: &TokenId, account_id: &AccountId) -> Balance {
+        self.balances
+            .get(token_id)
+            .and_then(|balances| balances.get(account_id))
+            .unwrap_or(0)
+    }
+
+    fn set_balance(&mut self, token_id: &TokenId, account_id: &AccountId, balance: Balance) {
+        let mut token_balances = self.balances.get(token_id).unwrap_or_else(|| {
+            LookupMap::new(StorageKey::BalancesInner {
+                token_id_hash: env::sha256(token_id.as_bytes()),
+            })
+        });
+        token_balances.insert(account_id, &balance);
+        self.balances.insert(token_id, &token_balances);
+    }
+
+    fn internal_deposit(&mut self, token_id: &TokenId, account_id: &AccountId, amount: Balance) {
+        let balance = self.balance_of(token_id, account_id);
+        self.set_balance(
+            token_id,
+            account_id,
+            balance.checked_add(amount).expect("Balance overflow"),
+        );
+        let supply = self.total_supply.get(token_id).unwrap_or(0);
+        self.total_supply
+            .insert(token_id, &supply.checked_add(amount).expect("Total supply overflow"));
+    }
+
+    fn internal_withdraw(&mut self, token_id: &TokenId, account_id: &AccountId, amount: Balance) {
+        let balance = self.balance_of(token_id, account_id);
+        self.set_balance(
+            token_id,
+            account_id,
+            balance.checked_sub(amount).expect("The account doesn't have enough balance"),
+        );
+        let supply = self.total_supply.get(token_id).unwrap_or(0);
+        self.total_supply
+            .insert(token_id, &supply.checked_sub(amount).expect("Total supply overflow"));
+    }
+
+    fn internal_transfer(
+        &mut self,
+        token_id: &TokenId,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: Balance,
+        memo: Option<String>,
+    ) {
+        assert_ne!(sender_id, receiver_id, "Sender and receiver should be different");
+        assert!(amount > 0, "The transfer amount should be a positive number");
+        self.internal_withdraw(token_id, sender_id, amount);
+        self.internal_deposit(token_id, receiver_id, amount);
+        MtTransfer {
+            old_owner_id: sender_id,
+            new_owner_id: receiver_id,
+            token_ids: std::slice::from_ref(token_id),
+            amounts: &[amount.into()],
+            memo: memo.as_deref(),
+        }
+        .emit();
+    }
+
+    /// Like `internal_transfer`, but splits `amount` between `receiver_id` and `fee_recipient`
+    /// according to `fee_basis_points`, emitting a separate transfer event for each leg. A zero
+    /// fee behaves identically to `internal_transfer`. Returns the post-fee amount credited to
+    /// `receiver_id`.
+    fn internal_transfer_with_fee(
+        &mut self,
+        token_id: &TokenId,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: Balance,
+        memo: Option<String>,
+    ) -> Balance {
+        assert_ne!(sender_id, receiver_id, "Sender and receiver should be different");
+        assert!(amount > 0, "The transfer amount should be a positive number");
+        let fee = amount
+            .checked_mul(self.fee_basis_points as Balance)
+            .expect("Fee calculation overflow")
+            / 10_000;
+        let remainder = amount - fee;
+
+        self.internal_withdraw(token_id, sender_id, amount);
+        self.internal_deposit(token_id, receiver_id, remainder);
+        MtTransfer {
+            old_owner_id: sender_id,
+            new_owner_id: receiver_id,
+            token_ids: std::slice::from_ref(token_id),
+            amounts: &[remainder.into()],
+            memo: memo.as_deref(),
+        }
+        .emit();
+
+        if fee > 0 {
+            let fee_recipient = self.fee_recipient.clone();
+            self.internal_deposit(token_id, &fee_recipient, fee);
+            MtTransfer {
+                old_owner_id: sender_id,
+                new_owner_id: &fee_recipient,
+                token_ids: std::slice::from_ref(token_id),
+                amounts: &[fee.into()],
+                memo: None,
+            }
+            .emit();
+        }
+        remainder
+    }
+
+    /// Like `internal_transfer_with_fee`, but fixed to `DEFAULT_TOKEN_ID` and emitting NEP-141
+    /// `ft_transfer` events instead of NEP-245 `mt_transfer`, for the single-token `ft_transfer`,
+    /// `ft_transfer_call` and `ft_transfer_with_reference` paths that emit NEP-141 events for both legs.
+    fn internal_ft_transfer_with_fee(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: Balance,
+        memo: Option<String>,
+    ) -> Balance {
+        assert_ne!(sender_id, receiver_id, "Sender and receiver should be different");
+        assert!(amount > 0, "The transfer amount should be a positive number");
+        let token_id = DEFAULT_TOKEN_ID.to_string();
+        let fee = amount
+            .checked_mul(self.fee_basis_points as Balance)
+            .expect("Fee calculation overflow")
+            / 10_000;
+        let remainder = amount - fee;
+
+        self.internal_withdraw(&token_id, sender_id, amount);
+        self.internal_deposit(&token_id, receiver_id, remainder);
+        FtTransfer {
+            old_owner_id: sender_id,
+            new_owner_id: receiver_id,
+            amount: remainder.into(),
+            memo: memo.as_deref(),
+        }
+        .emit();
+
+        if fee > 0 {
+            let fee_recipient = self.fee_recipient.clone();
+            self.internal_deposit(&token_id, &fee_recipient, fee);
+            FtTransfer {
+                old_owner_id: sender_id,
+                new_owner_id: &fee_recipient,
+                amount: fee.into(),
+                memo: None,
+            }
+            .emit();
+        }
+        remainder
+    }
+
+    fn internal_batch_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_ids: &[TokenId],
+        amounts: &[U128],
+        memo: Option<String>,
+    ) {
+        assert_eq!(token_ids.len(), amounts.len(), "token_ids and amounts length mismatch");
+        for (token_id, amount) in token_ids.iter().zip(amounts.iter()) {
+            self.internal_transfer(token_id, sender_id, receiver_id, amount.0, memo.clone());
+        }
+    }
+
+    /// Like `internal_batch_transfer`, but each leg goes through `internal_transfer_with_fee`.
+    /// Returns the post-fee amount credited to `receiver_id` for each leg, so callers that chain
+    /// a cross-contract notification (`mt_batch_transfer_call`) tell the receiver what it actually
+    /// holds rather than the pre-fee `amounts`.
+    fn internal_batch_transfer_with_fee(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_ids: &[TokenId],
+        amounts: &[U128],
+        memo: Option<String>,
+    ) -> Vec<U128> {
+        assert_eq!(token_ids.len(), amounts.len(), "token_ids and amounts length mismatch");
+        token_ids
+            .iter()
+            .zip(amounts.iter())
+            .map(|(token_id, amount)| {
+                U128(self.internal_transfer_with_fee(
+                    token_id,
+                    sender_id,
+                    receiver_id,
+                    amount.0,
+                    memo.clone(),
+                ))
+            })
+            .collect()
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
     use super::*;
 
-    use near_sdk::test_utils::{VMContextBuilder, accounts};
-    use near_sdk::{testing_env, env, Balance};
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
+    use near_sdk::testing_env;
     use near_sdk::MockedBlockchain;
 
-    const MINT_STORAGE_COST: u128 = 58700000000000000000000;
-    const TOTAL_SUPPLY: Balance = 1_000_000_000_000_000;
-    
-
     fn get_context(is_view: bool) -> VMContextBuilder {
         let mut builder = VMContextBuilder::new();
-        builder.
-        current_account_id(accounts(0))
-        .signer_account_id(accounts(0))
-        .predecessor_account_id(accounts(0))
-        .is_view(is_view);
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .is_view(is_view);
         builder
     }
 
     fn get_sample_metadata() -> FungibleTokenMetadata {
-        FungibleTokenMetadata { 
+        FungibleTokenMetadata {
             spec: "ft-1.0.0".to_string(),
             name: "ManhnvCoin".to_string(),
             symbol: "MNC".to_string(),
@@ -100,64 +855,117 @@ mod tests {
         }
     }
 
+    /// Pays `account_id`'s NEP-145 storage deposit so it can receive tokens in tests that don't
+    /// exercise `storage_deposit` itself.
+    fn register_account(contract: &mut Contract, context: &mut VMContextBuilder, account_id: AccountId) {
+        let min_balance = contract.storage_balance_bounds().min.0;
+        testing_env!(context.attached_deposit(min_balance).predecessor_account_id(account_id).build());
+        contract.storage_deposit(None, None);
+    }
+
     #[test]
     fn test_init_contract() {
         let mut context = get_context(false);
         testing_env!(context.build());
-        
-        // Init contract
+
         let metadata = get_sample_metadata();
-        let total_supply =  U128::from(587000000000000000000000000);
-        let mut contract = Contract::new(accounts(0), total_supply, metadata);
+        let total_supply = U128::from(587000000000000000000000000);
+        let contract = Contract::new(accounts(0), total_supply, metadata);
 
-        testing_env!(
-            context.storage_usage(env::storage_usage())
-            .attached_deposit(MINT_STORAGE_COST)
-            .predecessor_account_id(accounts(0))
-            .build()
-        );
+        testing_env!(context.is_view(true).build());
 
-        let balance = contract.ft_balance_of(accounts(0));
-        let total_supply_contract = contract.ft_total_supply();
+        let balance = contract.mt_balance_of(accounts(0), DEFAULT_TOKEN_ID.to_string());
+        let total_supply_contract = contract.mt_total_supply(DEFAULT_TOKEN_ID.to_string());
 
         assert_eq!(balance.0, total_supply_contract.0);
         assert_eq!(total_supply_contract.0, total_supply.0);
-        assert_eq!(balance.0, total_supply.0);
     }
 
     #[test]
-    fn test_transfer() {
+    fn test_batch_transfer() {
         let mut context = get_context(false);
         testing_env!(context.build());
         let metadata = get_sample_metadata();
         let total_supply = 1_000_000_000_000_000;
         let mut contract = Contract::new(accounts(0), total_supply.into(), metadata);
-        testing_env!(context
-            .storage_usage(env::storage_usage())
-            .attached_deposit(contract.storage_balance_bounds().min.into())
-            .predecessor_account_id(accounts(0))
-            .build());
-        // Paying for account registration, aka storage deposit
-        contract.storage_deposit(None, None);
+        register_account(&mut contract, &mut context, accounts(1).into());
 
-        testing_env!(context
-            .storage_usage(env::storage_usage())
-            .attached_deposit(1)
-            .predecessor_account_id(accounts(0))
-            .build());
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(0)).build());
         let transfer_amount = total_supply / 3;
-        contract.ft_transfer(accounts(1), transfer_amount.into(), None);
+        contract.mt_batch_transfer(
+            accounts(1),
+            vec![DEFAULT_TOKEN_ID.to_string()],
+            vec![transfer_amount.into()],
+            None,
+        );
 
-        testing_env!(context
-            .storage_usage(env::storage_usage())
-            .account_balance(env::account_balance())
-            .is_view(true)
-            .attached_deposit(0)
-            .build());
-        assert_eq!(contract.ft_balance_of(accounts(0)).0, (total_supply - transfer_amount));
-        assert_eq!(contract.ft_balance_of(accounts(1)).0, transfer_amount);
+        testing_env!(context.is_view(true).attached_deposit(0).build());
+        assert_eq!(
+            contract.mt_balance_of(accounts(0), DEFAULT_TOKEN_ID.to_string()).0,
+            total_supply - transfer_amount
+        );
+        assert_eq!(
+            contract.mt_balance_of(accounts(1), DEFAULT_TOKEN_ID.to_string()).0,
+            transfer_amount
+        );
     }
 
+    #[test]
+    fn test_transfer_fee() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let total_supply = 1_000_000_000_000_000;
+        let mut contract = Contract::new(accounts(0), total_supply.into(), metadata);
+        register_account(&mut contract, &mut context, accounts(1).into());
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.set_fee(100, accounts(2)); // 1%
+
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(0)).build());
+        let transfer_amount = 10_000;
+        contract.mt_batch_transfer(
+            accounts(1),
+            vec![DEFAULT_TOKEN_ID.to_string()],
+            vec![transfer_amount.into()],
+            None,
+        );
+
+        testing_env!(context.is_view(true).attached_deposit(0).build());
+        assert_eq!(
+            contract.mt_balance_of(accounts(1), DEFAULT_TOKEN_ID.to_string()).0,
+            transfer_amount - transfer_amount / 100
+        );
+        assert_eq!(
+            contract.mt_balance_of(accounts(2), DEFAULT_TOKEN_ID.to_string()).0,
+            transfer_amount / 100
+        );
+    }
+
+    #[test]
+    fn test_zero_fee_behaves_like_plain_transfer() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let total_supply = 1_000_000_000_000_000;
+        let mut contract = Contract::new(accounts(0), total_supply.into(), metadata);
+        register_account(&mut contract, &mut context, accounts(1).into());
+
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(0)).build());
+        let transfer_amount = 10_000;
+        contract.mt_batch_transfer(
+            accounts(1),
+            vec![DEFAULT_TOKEN_ID.to_string()],
+            vec![transfer_amount.into()],
+            None,
+        );
+
+        testing_env!(context.is_view(true).attached_deposit(0).build());
+        assert_eq!(
+            contract.mt_balance_of(accounts(1), DEFAULT_TOKEN_ID.to_string()).0,
+            transfer_amount
+        );
+    }
 
     #[test]
     fn test_mint() {
@@ -167,47 +975,257 @@ mod tests {
         let metadata = get_sample_metadata();
         let total_supply = 1_000_000_000_000_000;
         let mut contract = Contract::new(accounts(0), total_supply.into(), metadata);
-        testing_env!(context
-            .storage_usage(env::storage_usage())
-            .attached_deposit(contract.storage_balance_bounds().min.into())
-            .predecessor_account_id(accounts(1))
-            .build());
-        // Paying for account registration, aka storage deposit
-        contract.storage_deposit(None, None);
+        register_account(&mut contract, &mut context, accounts(1).into());
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.grant_role(accounts(2), Role::Minter);
 
-        // testing_env!(context
-        //     .storage_usage(env::storage_usage())
-        //     .attached_deposit(deposit_amount)
-        //     .predecessor_account_id(accounts(1))
-        //     .build());
-        // contract.mint(accounts(1));
-
-        testing_env!(context
-            .storage_usage(env::storage_usage())
-            .account_balance(env::account_balance())
-            .is_view(false)
-            .predecessor_account_id(accounts(2))
-            .attached_deposit(deposit_amount)
-            .build());
-        contract.mint(accounts(1));
-
-        testing_env!(context
-            .storage_usage(env::storage_usage())
-            .account_balance(env::account_balance())
-            .is_view(true)
-            .predecessor_account_id(accounts(1))
-            .build());
-
-        assert_eq!(contract.ft_balance_of(accounts(1)).0, deposit_amount * 2);
-
-        testing_env!(context
-            .storage_usage(env::storage_usage())
-            .account_balance(env::account_balance())
-            .is_view(false)
-            .predecessor_account_id(accounts(1))
-            .build());
-        let balance_before = accounts(1);
-        contract.withdraw(deposit_amount.into());
-        assert_eq!(contract.ft_balance_of(accounts(1)).0, deposit_amount);
-    }
-}
\ No newline at end of file
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.mt_mint(DEFAULT_TOKEN_ID.to_string(), accounts(1), deposit_amount.into(), None);
+
+        testing_env!(context.is_view(true).predecessor_account_id(accounts(1)).build());
+        assert_eq!(
+            contract.mt_balance_of(accounts(1), DEFAULT_TOKEN_ID.to_string()).0,
+            deposit_amount
+        );
+    }
+
+    #[test]
+    fn test_burn() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let total_supply = 1_000_000_000_000_000;
+        let mut contract = Contract::new(accounts(0), total_supply.into(), metadata);
+
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(0)).build());
+        let burn_amount = 10_000;
+        contract.mt_burn(DEFAULT_TOKEN_ID.to_string(), burn_amount.into());
+
+        let logs = get_logs();
+        assert!(
+            logs.iter().any(|log| log.contains("\"event\":\"mt_burn\"")),
+            "expected an mt_burn event log, got {:?}",
+            logs
+        );
+
+        testing_env!(context.is_view(true).attached_deposit(0).build());
+        assert_eq!(
+            contract.mt_balance_of(accounts(0), DEFAULT_TOKEN_ID.to_string()).0,
+            total_supply - burn_amount
+        );
+        assert_eq!(contract.mt_total_supply(DEFAULT_TOKEN_ID.to_string()).0, total_supply - burn_amount);
+    }
+
+    #[test]
+    fn test_mint_new_token_with_metadata() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let mut contract = Contract::new(accounts(0), 1_000_000.into(), metadata.clone());
+        register_account(&mut contract, &mut context, accounts(1).into());
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.mt_mint("1".to_string(), accounts(1), 500.into(), Some(metadata));
+
+        testing_env!(context.is_view(true).build());
+        assert_eq!(contract.mt_balance_of(accounts(1), "1".to_string()).0, 500);
+        assert_eq!(contract.mt_total_supply("1".to_string()).0, 500);
+    }
+
+    #[test]
+    fn test_ft_transfer_with_reference() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let total_supply = 1_000_000_000_000_000;
+        let mut contract = Contract::new(accounts(0), total_supply.into(), metadata);
+        register_account(&mut contract, &mut context, accounts(1).into());
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.set_fee(100, accounts(2)); // 1%
+
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(0)).build());
+        let transfer_amount = 10_000;
+        contract.ft_transfer_with_reference(
+            accounts(1),
+            transfer_amount.into(),
+            "invoice-42".to_string(),
+        );
+
+        let logs = get_logs();
+        assert!(
+            logs.iter().any(|log| log.starts_with("payment_reference_hash:")),
+            "expected a payment_reference_hash log, got {:?}",
+            logs
+        );
+        assert!(
+            logs.iter().any(|log| log.contains("\"event\":\"ft_transfer\"")),
+            "expected an ft_transfer event log, got {:?}",
+            logs
+        );
+
+        testing_env!(context.is_view(true).attached_deposit(0).build());
+        assert_eq!(
+            contract.mt_balance_of(accounts(1), DEFAULT_TOKEN_ID.to_string()).0,
+            transfer_amount - transfer_amount / 100
+        );
+        assert_eq!(
+            contract.mt_balance_of(accounts(2), DEFAULT_TOKEN_ID.to_string()).0,
+            transfer_amount / 100
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused")]
+    fn test_paused_contract_rejects_mint() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let mut contract = Contract::new(accounts(0), 1_000_000.into(), metadata.clone());
+        register_account(&mut contract, &mut context, accounts(1).into());
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.pause();
+        contract.mt_mint(DEFAULT_TOKEN_ID.to_string(), accounts(1), 1.into(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused")]
+    fn test_paused_contract_rejects_transfer() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let total_supply = 1_000_000;
+        let mut contract = Contract::new(accounts(0), total_supply.into(), metadata);
+        register_account(&mut contract, &mut context, accounts(1).into());
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.pause();
+
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(0)).build());
+        contract.mt_batch_transfer(
+            accounts(1),
+            vec![DEFAULT_TOKEN_ID.to_string()],
+            vec![1.into()],
+            None,
+        );
+    }
+
+    #[test]
+    fn test_unpause_restores_transfers() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let total_supply = 1_000_000;
+        let mut contract = Contract::new(accounts(0), total_supply.into(), metadata);
+        register_account(&mut contract, &mut context, accounts(1).into());
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.pause();
+        contract.unpause();
+
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(0)).build());
+        contract.mt_batch_transfer(
+            accounts(1),
+            vec![DEFAULT_TOKEN_ID.to_string()],
+            vec![1.into()],
+            None,
+        );
+
+        testing_env!(context.is_view(true).attached_deposit(0).build());
+        assert_eq!(contract.mt_balance_of(accounts(1), DEFAULT_TOKEN_ID.to_string()).0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the contract owner can call this method")]
+    fn test_pause_requires_owner() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let mut contract = Contract::new(accounts(0), 1_000_000.into(), metadata);
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.pause();
+    }
+
+    #[test]
+    #[should_panic(expected = "Caller does not have the required role")]
+    fn test_mint_requires_minter_role() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let mut contract = Contract::new(accounts(0), 1_000_000.into(), metadata);
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.mt_mint(DEFAULT_TOKEN_ID.to_string(), accounts(1), 1.into(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Caller does not have the required role")]
+    fn test_revoked_role_loses_access() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let mut contract = Contract::new(accounts(0), 1_000_000.into(), metadata);
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.grant_role(accounts(2), Role::Minter);
+        contract.revoke_role(accounts(2));
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.mt_mint(DEFAULT_TOKEN_ID.to_string(), accounts(1), 1.into(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Caller does not have the required role")]
+    fn test_grant_role_requires_admin() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let mut contract = Contract::new(accounts(0), 1_000_000.into(), metadata);
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.grant_role(accounts(2), Role::Minter);
+    }
+
+    #[test]
+    fn test_migrate_carries_over_balances_with_no_fee() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+        let metadata = get_sample_metadata();
+        let total_supply = 1_000_000;
+        let old = OldContract {
+            balances: {
+                let mut balances = LookupMap::new(StorageKey::Balances);
+                let mut inner = LookupMap::new(StorageKey::BalancesInner {
+                    token_id_hash: env::sha256(DEFAULT_TOKEN_ID.as_bytes()),
+                });
+                inner.insert(&accounts(0).into(), &total_supply);
+                balances.insert(&DEFAULT_TOKEN_ID.to_string(), &inner);
+                balances
+            },
+            total_supply: {
+                let mut supply = LookupMap::new(StorageKey::TotalSupply);
+                supply.insert(&DEFAULT_TOKEN_ID.to_string(), &total_supply);
+                supply
+            },
+            token_metadata: {
+                let mut token_metadata = LookupMap::new(StorageKey::TokenMetadata);
+                token_metadata.insert(&DEFAULT_TOKEN_ID.to_string(), &metadata);
+                token_metadata
+            },
+            owner_id: accounts(0).into(),
+            roles: LookupMap::new(StorageKey::Roles),
+            paused: false,
+        };
+
+        let contract: Contract = old.into();
+        assert_eq!(
+            contract.mt_balance_of(accounts(0), DEFAULT_TOKEN_ID.to_string()).0,
+            total_supply
+        );
+        assert_eq!(contract.fee_basis_points, 0);
+        assert_eq!(contract.fee_recipient, AccountId::from(accounts(0)));
+    }
+}
@@ -0,0 +1,35 @@
+use near_sdk::json_types::U128;
+use near_sdk::{ext_contract, AccountId, Gas};
+
+use crate::TokenId;
+
+/// Gas allocated to the receiver's `mt_on_transfer` call made by `mt_batch_transfer_call`.
+pub const GAS_FOR_MT_ON_TRANSFER: Gas = 25_000_000_000_000;
+/// Gas allocated to `mt_resolve_transfer`, chained after `mt_on_transfer` returns.
+pub const GAS_FOR_RESOLVE_TRANSFER: Gas = 15_000_000_000_000;
+
+/// Callback implemented by contracts that want to receive tokens via `mt_batch_transfer_call`,
+/// mirroring `FungibleTokenReceiver` but batched over parallel `token_ids`/`amounts`.
+#[ext_contract(ext_mt_receiver)]
+pub trait MultiTokenReceiver {
+    fn mt_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        msg: String,
+    ) -> Vec<U128>;
+}
+
+/// Private callback on this contract that settles `mt_batch_transfer_call`, refunding whatever
+/// `mt_on_transfer` reported as unused back to the sender.
+#[ext_contract(ext_self)]
+pub trait MultiTokenResolver {
+    fn mt_resolve_transfer(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        delivered: Vec<U128>,
+    ) -> Vec<U128>;
+}
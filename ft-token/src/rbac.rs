@@ -0,0 +1,20 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+/// Roles that can be granted to an account by an `Admin`.
+///
+/// `Admin` is treated as a superset of every other role, so an admin never
+/// needs to also hold `Minter` to pass an `assert_has_role(Role::Minter)` check.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    Admin,
+    Minter,
+}
+
+impl Role {
+    /// Whether an account holding `self` satisfies a check for `required`.
+    pub fn satisfies(&self, required: Role) -> bool {
+        *self == Role::Admin || *self == required
+    }
+}